@@ -89,17 +89,384 @@ pub fn states_at_instant(bodies: &[i32], cb_id: i32, et: f64) -> Result<Array1<f
 	Ok(state)
 }
 
+/// Per-body position/velocity error statistics produced by [`validate_spk`], in
+/// meters and meters/second
+#[derive(Debug, Clone, Copy)]
+pub struct BodyValidation {
+	pub body: i32,
+	pub max_position_error_m: f64,
+	pub rms_position_error_m: f64,
+	pub max_velocity_error_m_s: f64,
+	pub rms_velocity_error_m_s: f64,
+}
+
+/// Round-trip validation report produced by [`validate_spk`]
+#[derive(Debug, Clone)]
+pub struct ValidationReport {
+	pub bodies: Vec<BodyValidation>,
+}
+
+fn rms(errors: &[f64]) -> f64 {
+	(errors.iter().map(|e| e * e).sum::<f64>() / errors.len() as f64).sqrt()
+}
+
+/// Compare a reference state (meters, m/s) against a state read back from a kernel
+/// (km, km/s as returned by `spkezr`), accumulating position/velocity error norms
+fn push_errors(
+	reference_m: &Array1<f64>,
+	reconstructed_km: &Array1<f64>,
+	pos_errors: &mut Vec<f64>,
+	vel_errors: &mut Vec<f64>,
+) {
+	let diff = reference_m - &(reconstructed_km * 1000.0);
+	let pos_err = diff.slice(s![0..3]).mapv(|v| v * v).sum().sqrt();
+	let vel_err = diff.slice(s![3..6]).mapv(|v| v * v).sum().sqrt();
+	pos_errors.push(pos_err);
+	vel_errors.push(vel_err);
+}
+
+/// Furnish the SPK file just produced by [`write_to_spk`] and compare the states it
+/// reconstructs via `spkezr` (through [`state_at_instant`]) against the in-memory
+/// propagated `states`, both at the node epochs in `ets` and at the midpoints
+/// between consecutive nodes (for which the reference state is the midpoint-time
+/// linear interpolant of the two bracketing in-memory states). Reports per-body
+/// max/RMS position and velocity error.
+///
+/// `ets`/`states` must be the full, pre-decimation propagator output, not the
+/// (possibly much sparser) arrays actually written to `fname`: the midpoint
+/// reference is only a meaningful ground truth if consecutive samples are close
+/// enough for the linear blend's own curvature error to be negligible next to the
+/// errors this is meant to catch. Passing the already-decimated arrays just
+/// compares one interpolation scheme against another with no physical ground
+/// truth to validate against.
+pub fn validate_spk(
+	fname: &str,
+	bodies: &[i32],
+	states: &[Array1<f64>],
+	ets: &[f64],
+	cb_id: i32,
+) -> Result<ValidationReport, String> {
+	set_error_handling("return", "short");
+
+	unsafe { spice::c::furnsh_c(spice::cstr!(fname)) };
+
+	if unsafe { spice::c::failed_c() } != 0 {
+		return Err(format!(
+			"Failed to furnish SPK file for validation: {}",
+			get_err_msg()
+		));
+	}
+
+	// Always unload the furnished kernel before returning, even if the per-body
+	// comparison below bails out early with `?` on a transient spkezr failure.
+	let result: Result<ValidationReport, String> = (|| {
+		let mut report = ValidationReport { bodies: Vec::new() };
+
+		// As in `write_to_spk`, states are taken relative to the observing body
+		// when its trajectory was also propagated
+		let cb_idx = bodies.iter().position(|&b| b == cb_id);
+		let relative_state = |w: usize, idx: usize| {
+			let mut s = states[w].slice(s![(idx * 6)..(idx * 6 + 6)]).to_owned();
+			if let Some(cb_idx) = cb_idx {
+				s -= &states[w].slice(s![(cb_idx * 6)..(cb_idx * 6 + 6)]);
+			}
+			s
+		};
+
+		for (idx, &id) in bodies.iter().enumerate() {
+			// Skip observing body
+			if id == cb_id {
+				continue;
+			}
+
+			let mut pos_errors = Vec::new();
+			let mut vel_errors = Vec::new();
+
+			for w in 0..ets.len() {
+				let node_ref = relative_state(w, idx);
+				let reconstructed = state_at_instant(id, cb_id, ets[w])?;
+				push_errors(&node_ref, &reconstructed, &mut pos_errors, &mut vel_errors);
+
+				if w + 1 < ets.len() {
+					let next_ref = relative_state(w + 1, idx);
+					let midpoint_et = (ets[w] + ets[w + 1]) / 2.0;
+					let midpoint_ref = (&node_ref + &next_ref) / 2.0;
+					let reconstructed_mid = state_at_instant(id, cb_id, midpoint_et)?;
+					push_errors(
+						&midpoint_ref,
+						&reconstructed_mid,
+						&mut pos_errors,
+						&mut vel_errors,
+					);
+				}
+			}
+
+			report.bodies.push(BodyValidation {
+				body: id,
+				max_position_error_m: pos_errors.iter().cloned().fold(0.0, f64::max),
+				rms_position_error_m: rms(&pos_errors),
+				max_velocity_error_m_s: vel_errors.iter().cloned().fold(0.0, f64::max),
+				rms_velocity_error_m_s: rms(&vel_errors),
+			});
+		}
+
+		Ok(report)
+	})();
+
+	// In "return" mode SPICE routines do nothing while an error is signalled, so
+	// clear an spkezr failure (its message is already in `result`) before unloading
+	if result.is_err() {
+		unsafe { spice::c::reset_c() };
+	}
+
+	unsafe { spice::c::unload_c(spice::cstr!(fname)) };
+
+	let report = result?;
+
+	if unsafe { spice::c::failed_c() } != 0 {
+		return Err(format!(
+			"Failed to unload SPK file after validation: {}",
+			get_err_msg()
+		));
+	}
+
+	Ok(report)
+}
+
+/// Node selection strategy used by [`write_to_spk`] to decide which epochs to keep
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DecimationMode {
+	/// Keep a uniformly-spaced fraction of the nodes, blind to trajectory curvature
+	FixedFraction(f32),
+	/// Seed the kept set with the first and last epoch, then greedily add back
+	/// whichever dropped epoch has the largest cubic-Hermite reconstruction error
+	/// (using its two bracketing kept neighbors' position and velocity) until every
+	/// dropped epoch reconstructs within `tolerance_m` meters
+	Tolerance { tolerance_m: f64 },
+}
+
+/// Cubic Hermite position interpolant between two bracketing (epoch, state) nodes,
+/// using the position and velocity carried by each node
+fn hermite_position(
+	t0: f64,
+	s0: ArrayView1<f64>,
+	t1: f64,
+	s1: ArrayView1<f64>,
+	t: f64,
+) -> Array1<f64> {
+	let dt = t1 - t0;
+	let tau = (t - t0) / dt;
+	let tau2 = tau * tau;
+	let tau3 = tau2 * tau;
+
+	let h_pos0 = 2.0 * tau3 - 3.0 * tau2 + 1.0;
+	let h_vel0 = tau3 - 2.0 * tau2 + tau;
+	let h_pos1 = -2.0 * tau3 + 3.0 * tau2;
+	let h_vel1 = tau3 - tau2;
+
+	&s0.slice(s![0..3]) * h_pos0
+		+ &s0.slice(s![3..6]) * (dt * h_vel0)
+		+ &s1.slice(s![0..3]) * h_pos1
+		+ &s1.slice(s![3..6]) * (dt * h_vel1)
+}
+
+/// Greedily select the minimal subset of epochs such that, for every dropped
+/// epoch and every body, the cubic-Hermite reconstruction from the kept neighbors
+/// is within `tolerance_m` meters of the in-memory propagated position
+fn decimate_by_tolerance<'a>(
+	bodies: &[i32],
+	states: &'a [Array1<f64>],
+	ets: &'a [f64],
+	tolerance_m: f64,
+) -> Result<(Vec<f64>, Vec<&'a Array1<f64>>), String> {
+	let n = ets.len();
+	if n < 2 {
+		return Err("Need at least 2 epochs to perform tolerance-based decimation".to_string());
+	}
+
+	let mut kept = std::collections::BTreeSet::new();
+	kept.insert(0usize);
+	kept.insert(n - 1);
+
+	loop {
+		let mut worst: Option<(usize, f64)> = None;
+
+		for i in 0..n {
+			if kept.contains(&i) {
+				continue;
+			}
+
+			let lo = *kept.range(..i).next_back().unwrap();
+			let hi = *kept.range(i..).next().unwrap();
+
+			let mut max_err = 0.0f64;
+			for (bidx, _) in bodies.iter().enumerate() {
+				let slice = s![(bidx * 6)..(bidx * 6 + 6)];
+				let interp = hermite_position(
+					ets[lo],
+					states[lo].slice(slice),
+					ets[hi],
+					states[hi].slice(slice),
+					ets[i],
+				);
+				let actual = states[i].slice(s![(bidx * 6)..(bidx * 6 + 3)]);
+				let err = (&interp - &actual).mapv(|v| v * v).sum().sqrt();
+				max_err = max_err.max(err);
+			}
+
+			let is_new_worst = match worst {
+				Some((_, e)) => max_err > e,
+				None => true,
+			};
+			if is_new_worst {
+				worst = Some((i, max_err));
+			}
+		}
+
+		match worst {
+			Some((i, err)) if err > tolerance_m => {
+				kept.insert(i);
+			}
+			_ => break,
+		}
+	}
+
+	let ets_out = kept.iter().map(|&i| ets[i]).collect();
+	let states_out = kept.iter().map(|&i| &states[i]).collect();
+
+	Ok((ets_out, states_out))
+}
+
+/// Bounds on the size of each SPK segment written by [`write_to_spk`]. A `None`
+/// field leaves that dimension unbounded. Leaving both fields `None` reproduces
+/// the previous behavior of a single segment spanning the whole trajectory.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SegmentLimits {
+	pub max_nodes: Option<usize>,
+	pub max_span_s: Option<f64>,
+}
+
+/// Partition `[0, ets.len())` into segments of at most `limits.max_nodes` nodes
+/// and `limits.max_span_s` seconds, each with at least `min_nodes` nodes.
+/// Consecutive segments share their boundary nodes (the next segment starts
+/// `min_nodes - 1` nodes before the previous one ends) so that the interpolants
+/// on either side of a seam are built from, and therefore agree on, the same data.
+///
+/// `min_nodes` takes precedence over `max_span_s`: a segment that would otherwise
+/// end up below `min_nodes` nodes is grown past the span cap rather than left
+/// short, since SPICE's own node-count floor for the interpolation degree is not
+/// negotiable. The same precedence applies to forward progress — if a dense
+/// cluster of epochs followed by a gap would make the span cap shrink a segment
+/// back to (or past) the previous segment's end, the segment is grown past the
+/// span cap instead, since a segment that adds no new coverage is a wasted write.
+fn partition_into_segments(
+	ets: &[f64],
+	min_nodes: usize,
+	limits: SegmentLimits,
+) -> Result<Vec<(usize, usize)>, String> {
+	let n = ets.len();
+	if n < min_nodes {
+		return Err(format!(
+			"Only {n} nodes are available but each segment needs at least {min_nodes}"
+		));
+	}
+
+	let max_nodes = limits.max_nodes.unwrap_or(n).max(min_nodes);
+	let overlap = min_nodes.saturating_sub(1);
+
+	let mut segments = Vec::new();
+	let mut start = 0usize;
+
+	loop {
+		let mut end = (start + max_nodes - 1).min(n - 1);
+
+		if let Some(max_span_s) = limits.max_span_s {
+			while end > start && ets[end] - ets[start] > max_span_s {
+				end -= 1;
+			}
+		}
+
+		if end - start + 1 < min_nodes {
+			end = (start + min_nodes - 1).min(n - 1);
+		}
+
+		// A span-capped `end` can regress to (or never pass) the previous
+		// segment's `end` when a dense cluster is followed by a sparse gap,
+		// which would make this segment a subset of the one before it. Force
+		// it to strictly advance, even past `max_span_s`, so every segment
+		// contributes new coverage.
+		if let Some(&(_, prev_end)) = segments.last() {
+			if end <= prev_end {
+				end = (prev_end + 1).min(n - 1);
+			}
+		}
+
+		segments.push((start, end));
+
+		if end >= n - 1 {
+			break;
+		}
+
+		start = end.saturating_sub(overlap).max(start + 1);
+	}
+
+	// The overlap step can leave the final segment shorter than `min_nodes`;
+	// fold it into the one before it rather than writing an undersized segment.
+	if segments.len() > 1 {
+		let (last_start, last_end) = *segments.last().unwrap();
+		if last_end - last_start + 1 < min_nodes {
+			segments.pop();
+			let (prev_start, _) = segments.pop().unwrap();
+			segments.push((prev_start, last_end));
+		}
+	}
+
+	Ok(segments)
+}
+
+/// SPK segment writer to use in [`write_to_spk`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpkType {
+	/// Type 9: Lagrange interpolation of fixed degree, position and velocity rows
+	/// are interpolated independently of one another
+	Lagrange9 { degree: i32 },
+	/// Type 13: Hermite interpolation, matching both the sampled state and its
+	/// derivative at each node. `degree` must be odd and the resulting window
+	/// (`degree + 1`) must not exceed the number of nodes being written
+	Hermite13 { degree: i32 },
+}
+
+impl SpkType {
+	/// Interpolation degree, regardless of which variant is in use
+	fn degree(&self) -> i32 {
+		match *self {
+			SpkType::Lagrange9 { degree } | SpkType::Hermite13 { degree } => degree,
+		}
+	}
+}
+
 /// Write data contained in system to SPK file
+#[allow(clippy::too_many_arguments)]
 pub fn write_to_spk(
 	fname: &str,
 	bodies: &[i32],
 	states: &[Array1<f64>],
 	ets: &[f64],
 	cb_id: i32,
-	fraction_to_save: f32,
+	decimation: DecimationMode,
+	spk_type: SpkType,
+	segment_limits: SegmentLimits,
 ) -> Result<(), String> {
-	if !(0.0..=1.0).contains(&fraction_to_save) {
-		return Err("Please supply a fraction_to_save value between 0 and 1".to_string());
+	if let DecimationMode::FixedFraction(fraction_to_save) = decimation {
+		if !(0.0..=1.0).contains(&fraction_to_save) {
+			return Err("Please supply a fraction_to_save value between 0 and 1".to_string());
+		}
+	}
+
+	if let SpkType::Hermite13 { degree } = spk_type {
+		if degree % 2 == 0 {
+			return Err(format!("Hermite13 degree must be odd, got {degree}"));
+		}
 	}
 
 	set_error_handling("return", "short");
@@ -123,26 +490,28 @@ pub fn write_to_spk(
 	}
 
 	// Extract states to actually write to the file
-	let steps_to_skip = (1.0 / fraction_to_save) as usize;
-	let mut ets = ets
-		.iter()
-		.step_by(steps_to_skip)
-		.cloned()
-		.collect::<Vec<f64>>();
-	let states = states
-		.iter()
-		.step_by(steps_to_skip)
-		.collect::<Vec<&Array1<f64>>>();
+	let (ets, states) = match decimation {
+		DecimationMode::FixedFraction(fraction_to_save) => {
+			let steps_to_skip = (1.0 / fraction_to_save) as usize;
+			let ets = ets.iter().step_by(steps_to_skip).cloned().collect();
+			let states = states.iter().step_by(steps_to_skip).collect();
+			(ets, states)
+		}
+		DecimationMode::Tolerance { tolerance_m } => {
+			decimate_by_tolerance(bodies, states, ets, tolerance_m)?
+		}
+	};
+
+	let min_nodes = spk_type.degree() as usize + 1;
+	let segments = partition_into_segments(&ets, min_nodes, segment_limits)?;
 
 	// If the observing bodies trajectory was also propagated, assemble a state matrix for that body
 	// that can be substracted from other bodies state matrices to yield state relative to observing body
-	let cb_states_matrix_km = bodies.iter().position(|&id| id == cb_id).map(|idx| {
-		let cb_states = states
+	let cb_states = bodies.iter().position(|&id| id == cb_id).map(|idx| {
+		states
 			.iter()
 			.map(|&s| s.slice(s![(idx * 6)..(idx * 6 + 6)]))
-			.collect::<Vec<_>>();
-
-		concatenate(Axis(0), &cb_states).unwrap() / 1000f64
+			.collect::<Vec<_>>()
 	});
 
 	for (idx, &id) in bodies.iter().enumerate() {
@@ -157,37 +526,73 @@ pub fn write_to_spk(
 			.map(|&s| s.slice(s![(idx * 6)..(idx * 6 + 6)]))
 			.collect::<Vec<ArrayView1<f64>>>();
 
-		let mut states_matrix_km = (concatenate(Axis(0), &body_states[..]).unwrap()) / 1000f64;
-
-		if let Some(ref cb_states_matrix_km) = cb_states_matrix_km {
-			states_matrix_km -= cb_states_matrix_km;
-		}
-
-		unsafe {
-			spice::c::spkw09_c(
-				// Handle for previously created, opened SPK file
-				handle,
-				// Target body ID
-				id,
-				// Observing body ID
-				cb_id,
-				// Reference frame
-				spice::cstr!("J2000"),
-				// t0
-				ets[0],
-				// tfinal
-				ets[ets.len() - 1],
-				// Segment identifier
-				spice::cstr!(format!("Position of {} relative to {}", id, cb_id)),
-				// Degree of polynomial to be used for lagrange interpolation. Currently somewhat arbitrary.
-				7,
-				// Number of states/epochs
-				body_states.len() as i32,
-				// Pointer to beginning of state matrix
-				states_matrix_km.as_mut_ptr().cast(),
-				// Pointer to beginning of epoch vec
-				ets.as_mut_ptr(),
-			)
+		for (seg_idx, &(start, end)) in segments.iter().enumerate() {
+			let mut states_matrix_km =
+				(concatenate(Axis(0), &body_states[start..=end]).unwrap()) / 1000f64;
+
+			if let Some(ref cb_states) = cb_states {
+				states_matrix_km -= &(concatenate(Axis(0), &cb_states[start..=end]).unwrap() / 1000f64);
+			}
+
+			let mut seg_ets = ets[start..=end].to_vec();
+			let n_nodes = (end - start + 1) as i32;
+			let segid = spice::cstr!(format!(
+				"Position of {} relative to {}, segment {}",
+				id, cb_id, seg_idx
+			));
+
+			unsafe {
+				match spk_type {
+					SpkType::Lagrange9 { degree } => spice::c::spkw09_c(
+						// Handle for previously created, opened SPK file
+						handle,
+						// Target body ID
+						id,
+						// Observing body ID
+						cb_id,
+						// Reference frame
+						spice::cstr!("J2000"),
+						// t0
+						seg_ets[0],
+						// tfinal
+						seg_ets[n_nodes as usize - 1],
+						// Segment identifier
+						segid,
+						// Degree of polynomial to be used for lagrange interpolation.
+						degree,
+						// Number of states/epochs
+						n_nodes,
+						// Pointer to beginning of state matrix
+						states_matrix_km.as_mut_ptr().cast(),
+						// Pointer to beginning of epoch vec
+						seg_ets.as_mut_ptr(),
+					),
+					SpkType::Hermite13 { degree } => spice::c::spkw13_c(
+						// Handle for previously created, opened SPK file
+						handle,
+						// Target body ID
+						id,
+						// Observing body ID
+						cb_id,
+						// Reference frame
+						spice::cstr!("J2000"),
+						// t0
+						seg_ets[0],
+						// tfinal
+						seg_ets[n_nodes as usize - 1],
+						// Segment identifier
+						segid,
+						// Degree of polynomial used for Hermite interpolation; must be odd.
+						degree,
+						// Number of states/epochs
+						n_nodes,
+						// Pointer to beginning of state matrix
+						states_matrix_km.as_mut_ptr().cast(),
+						// Pointer to beginning of epoch vec
+						seg_ets.as_mut_ptr(),
+					),
+				}
+			}
 		}
 	}
 
@@ -207,3 +612,197 @@ pub fn write_to_spk(
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn state6(pos: [f64; 3], vel: [f64; 3]) -> Array1<f64> {
+		arr1(&[pos[0], pos[1], pos[2], vel[0], vel[1], vel[2]])
+	}
+
+	#[test]
+	fn hermite_position_reproduces_linear_motion_exactly() {
+		let v = [1.0, 2.0, 3.0];
+		let s0 = state6([0.0, 0.0, 0.0], v);
+		let s1 = state6([10.0, 20.0, 30.0], v);
+
+		let got = hermite_position(0.0, s0.view(), 10.0, s1.view(), 4.0);
+
+		assert!((got[0] - 4.0).abs() < 1e-9);
+		assert!((got[1] - 8.0).abs() < 1e-9);
+		assert!((got[2] - 12.0).abs() < 1e-9);
+	}
+
+	#[test]
+	fn hermite_position_matches_nodes_at_endpoints() {
+		let s0 = state6([1.0, 2.0, 3.0], [0.1, 0.2, 0.3]);
+		let s1 = state6([4.0, 5.0, 6.0], [0.4, 0.5, 0.6]);
+
+		let at_t0 = hermite_position(0.0, s0.view(), 5.0, s1.view(), 0.0);
+		let at_t1 = hermite_position(0.0, s0.view(), 5.0, s1.view(), 5.0);
+
+		for i in 0..3 {
+			assert!((at_t0[i] - s0[i]).abs() < 1e-9);
+			assert!((at_t1[i] - s1[i]).abs() < 1e-9);
+		}
+	}
+
+	fn linear_trajectory(n: usize) -> (Vec<f64>, Vec<Array1<f64>>) {
+		let v = [1.0, -2.0, 0.5];
+		let ets: Vec<f64> = (0..n).map(|i| i as f64).collect();
+		let states = ets
+			.iter()
+			.map(|&t| state6([v[0] * t, v[1] * t, v[2] * t], v))
+			.collect();
+		(ets, states)
+	}
+
+	fn sinusoidal_trajectory(n: usize) -> (Vec<f64>, Vec<Array1<f64>>) {
+		let span = 2.0 * std::f64::consts::PI;
+		let ets: Vec<f64> = (0..n).map(|i| i as f64 * span / (n as f64 - 1.0)).collect();
+		let states = ets
+			.iter()
+			.map(|&t| state6([t.sin(), 0.0, 0.0], [t.cos(), 0.0, 0.0]))
+			.collect();
+		(ets, states)
+	}
+
+	#[test]
+	fn decimate_by_tolerance_keeps_only_endpoints_for_linear_motion() {
+		let (ets, states) = linear_trajectory(10);
+		let bodies = [1];
+
+		let (out_ets, _) = decimate_by_tolerance(&bodies, &states, &ets, 1e-6).unwrap();
+
+		// Hermite interpolation is exact for linear motion, so no interior node
+		// should ever need to be kept, regardless of how tight the tolerance is.
+		assert_eq!(out_ets, vec![ets[0], ets[9]]);
+	}
+
+	#[test]
+	fn decimate_by_tolerance_adds_nodes_for_curved_motion_under_tight_tolerance() {
+		let (ets, states) = sinusoidal_trajectory(20);
+		let bodies = [1];
+
+		let (loose, _) = decimate_by_tolerance(&bodies, &states, &ets, 10.0).unwrap();
+		assert_eq!(loose.len(), 2);
+
+		let (tight, _) = decimate_by_tolerance(&bodies, &states, &ets, 1e-6).unwrap();
+		assert!(tight.len() > 2);
+		assert_eq!(tight.first(), Some(&ets[0]));
+		assert_eq!(tight.last(), Some(&ets[ets.len() - 1]));
+	}
+
+	#[test]
+	fn decimate_by_tolerance_rejects_single_epoch() {
+		let ets = vec![0.0];
+		let states = vec![state6([0.0; 3], [0.0; 3])];
+
+		assert!(decimate_by_tolerance(&[1], &states, &ets, 1.0).is_err());
+	}
+}
+
+#[cfg(test)]
+mod segment_tests {
+	use super::*;
+
+	#[test]
+	fn partition_rejects_too_few_nodes() {
+		let ets = vec![0.0, 1.0];
+		assert!(partition_into_segments(&ets, 3, SegmentLimits::default()).is_err());
+	}
+
+	#[test]
+	fn partition_is_a_single_segment_when_unbounded() {
+		let ets: Vec<f64> = (0..20).map(|i| i as f64).collect();
+		let segments = partition_into_segments(&ets, 4, SegmentLimits::default()).unwrap();
+		assert_eq!(segments, vec![(0, 19)]);
+	}
+
+	#[test]
+	fn partition_overlaps_by_min_nodes_minus_one_when_max_nodes_bounded() {
+		let ets: Vec<f64> = (0..6).map(|i| i as f64).collect();
+		let limits = SegmentLimits {
+			max_nodes: Some(3),
+			max_span_s: None,
+		};
+
+		let segments = partition_into_segments(&ets, 2, limits).unwrap();
+
+		assert_eq!(segments, vec![(0, 2), (1, 3), (2, 4), (3, 5)]);
+	}
+
+	#[test]
+	fn partition_respects_max_span_even_with_unbounded_node_count() {
+		// Dense cluster followed by widely separated epochs: with no max_nodes
+		// limit the span cap alone must still force multiple segments.
+		let ets = vec![0.0, 1.0, 2.0, 3.0, 10.0, 11.0, 12.0];
+		let limits = SegmentLimits {
+			max_nodes: None,
+			max_span_s: Some(4.0),
+		};
+
+		let segments = partition_into_segments(&ets, 3, limits).unwrap();
+
+		assert!(segments.len() > 1);
+		assert_eq!(segments.first().unwrap().0, 0);
+		assert_eq!(segments.last().unwrap().1, ets.len() - 1);
+		for &(start, end) in &segments {
+			assert!(end - start + 1 >= 3, "segment ({start}, {end}) is undersized");
+		}
+	}
+
+	#[test]
+	fn partition_segment_ends_strictly_advance_past_dense_then_sparse_cluster() {
+		// A dense cluster of epochs followed by a gap makes the span cap want to
+		// shrink `end` back to an index the previous segment already covers;
+		// every segment must still add coverage the one before it didn't have.
+		let ets = vec![0.0, 1.0, 2.0, 3.0, 10.0, 11.0, 12.0];
+		let limits = SegmentLimits {
+			max_nodes: None,
+			max_span_s: Some(4.0),
+		};
+
+		let segments = partition_into_segments(&ets, 3, limits).unwrap();
+
+		for window in segments.windows(2) {
+			let (_, prev_end) = window[0];
+			let (_, next_end) = window[1];
+			assert!(
+				next_end > prev_end,
+				"segment ending at {next_end} adds no coverage past the previous segment's end {prev_end}"
+			);
+		}
+	}
+
+	#[test]
+	fn partition_every_segment_covers_at_least_min_nodes() {
+		// An irregular spacing that exercises both the max_nodes clamp and the
+		// max_span_s clamp in the same run.
+		let ets = vec![
+			0.0, 0.5, 1.0, 1.5, 2.0, 2.5, 50.0, 50.5, 51.0, 100.0, 100.5, 101.0, 101.5,
+		];
+		let limits = SegmentLimits {
+			max_nodes: Some(5),
+			max_span_s: Some(3.0),
+		};
+		let min_nodes = 4;
+
+		let segments = partition_into_segments(&ets, min_nodes, limits).unwrap();
+
+		assert_eq!(segments.first().unwrap().0, 0);
+		assert_eq!(segments.last().unwrap().1, ets.len() - 1);
+		for window in segments.windows(2) {
+			let (_, prev_end) = window[0];
+			let (next_start, _) = window[1];
+			assert!(next_start <= prev_end, "segments must share a boundary node");
+		}
+		for &(start, end) in &segments {
+			assert!(
+				end - start + 1 >= min_nodes,
+				"segment ({start}, {end}) has fewer than {min_nodes} nodes"
+			);
+		}
+	}
+}